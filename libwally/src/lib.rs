@@ -0,0 +1,2 @@
+pub mod package_id;
+pub mod package_index;
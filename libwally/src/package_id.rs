@@ -0,0 +1,35 @@
+/// A package's scope and name, e.g. `acme/widget`.
+pub struct PackageName {
+    scope: String,
+    name: String,
+}
+
+impl PackageName {
+    pub fn scope(&self) -> &str {
+        &self.scope
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// Identifies a published package.
+pub struct PackageId {
+    name: PackageName,
+}
+
+impl PackageId {
+    pub fn new(scope: &str, name: &str) -> Self {
+        PackageId {
+            name: PackageName {
+                scope: scope.to_lowercase(),
+                name: name.to_lowercase(),
+            },
+        }
+    }
+
+    pub fn name(&self) -> &PackageName {
+        &self.name
+    }
+}
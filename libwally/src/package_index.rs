@@ -0,0 +1,119 @@
+use std::{collections::HashMap, sync::Mutex};
+
+use serde::{Deserialize, Serialize};
+
+/// The GitHub org or team a scope is configured to delegate ownership to, so
+/// any member (or a specific team) can publish without being listed as an
+/// individual scope owner.
+pub enum OrgScope {
+    Org(String),
+    Team { org: String, team: String },
+}
+
+/// What a Wally-issued personal access token is allowed to do, independent of
+/// whatever `AuthMode` the registry is configured with.
+#[derive(Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum TokenPermission {
+    Read,
+    Write,
+}
+
+/// A persisted Wally-issued personal access token, keyed by a SHA-256 hash of
+/// its secret -- never the secret itself.
+#[derive(Clone)]
+pub struct PersonalAccessTokenRecord {
+    pub token_hash: String,
+    pub owner_id: u64,
+    pub name: String,
+    pub permissions: Vec<TokenPermission>,
+    pub expires_at: Option<u64>,
+    pub revoked: bool,
+}
+
+/// The registry's scope-ownership metadata. A minimal in-memory
+/// implementation; the durable, file/DB-backed version lives upstream and
+/// isn't part of this auth-focused series.
+#[derive(Default)]
+pub struct PackageIndex {
+    scope_owners: Mutex<HashMap<String, Vec<u64>>>,
+    org_scopes: Mutex<HashMap<String, OrgScope>>,
+    personal_access_tokens: Mutex<Vec<PersonalAccessTokenRecord>>,
+}
+
+impl PackageIndex {
+    /// Whether `user_id` is a listed owner of `scope`.
+    pub fn is_scope_owner(&self, scope: &str, user_id: &u64) -> anyhow::Result<bool> {
+        Ok(self
+            .scope_owners
+            .lock()
+            .unwrap()
+            .get(scope)
+            .is_some_and(|owners| owners.contains(user_id)))
+    }
+
+    /// The user ids listed as owners of `scope`.
+    pub fn get_scope_owners(&self, scope: &str) -> anyhow::Result<Vec<u64>> {
+        Ok(self
+            .scope_owners
+            .lock()
+            .unwrap()
+            .get(scope)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    /// The GitHub org/team `scope` is configured to delegate ownership to, if
+    /// any.
+    pub fn org_scope(&self, scope: &str) -> anyhow::Result<Option<OrgScope>> {
+        Ok(match self.org_scopes.lock().unwrap().get(scope) {
+            Some(OrgScope::Org(org)) => Some(OrgScope::Org(org.clone())),
+            Some(OrgScope::Team { org, team }) => Some(OrgScope::Team {
+                org: org.clone(),
+                team: team.clone(),
+            }),
+            None => None,
+        })
+    }
+
+    /// Stores a newly-issued personal access token.
+    pub fn create_personal_access_token(
+        &self,
+        record: PersonalAccessTokenRecord,
+    ) -> anyhow::Result<()> {
+        self.personal_access_tokens.lock().unwrap().push(record);
+        Ok(())
+    }
+
+    /// Marks every unrevoked token named `name` belonging to `owner_id` as
+    /// revoked.
+    pub fn revoke_personal_access_token(&self, owner_id: u64, name: &str) -> anyhow::Result<()> {
+        for record in self.personal_access_tokens.lock().unwrap().iter_mut() {
+            if record.owner_id == owner_id && record.name == name {
+                record.revoked = true;
+            }
+        }
+        Ok(())
+    }
+
+    /// The non-revoked personal access tokens belonging to `owner_id`.
+    pub fn personal_access_tokens_for_owner(
+        &self,
+        owner_id: u64,
+    ) -> anyhow::Result<Vec<PersonalAccessTokenRecord>> {
+        Ok(self
+            .personal_access_tokens
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|record| record.owner_id == owner_id && !record.revoked)
+            .cloned()
+            .collect())
+    }
+
+    /// Every stored personal access token, revoked or not -- callers use this
+    /// to check a candidate secret's hash against every known token.
+    pub fn personal_access_tokens(&self) -> anyhow::Result<Vec<PersonalAccessTokenRecord>> {
+        Ok(self.personal_access_tokens.lock().unwrap().clone())
+    }
+}
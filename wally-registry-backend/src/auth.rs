@@ -1,19 +1,179 @@
-use std::{collections::HashMap, fmt};
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::Mutex,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
 
 use anyhow::{anyhow, format_err};
 use constant_time_eq::constant_time_eq;
-use libwally::{package_id::PackageId, package_index::PackageIndex};
+use hmac::{Hmac, Mac};
+use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use libwally::{
+    package_id::PackageId,
+    package_index::{OrgScope, PackageIndex, PersonalAccessTokenRecord, TokenPermission},
+};
+use rand::{distributions::Alphanumeric, Rng};
 use reqwest::{Client, StatusCode};
 use rocket::{
+    data::{Data, ToByteUnit},
+    fairing::{Fairing, Info, Kind},
     http::Status,
     request::{FromRequest, Outcome},
     Request, State,
 };
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
 use crate::error::Error;
 use crate::{config::Config, error::ApiErrorStatus};
 
+/// How long a session token minted from a GitHub OAuth token remains valid for.
+const SESSION_TOKEN_LIFETIME_SECS: u64 = 15 * 60;
+
+/// Default time a validated GitHub token is trusted for before
+/// [`GithubTokenCache`] re-checks it against the GitHub API.
+pub const DEFAULT_GITHUB_TOKEN_CACHE_TTL_SECS: u64 = 5 * 60;
+
+struct CachedGithubInfo {
+    info: GithubInfo,
+    inserted_at: Instant,
+}
+
+/// Memoizes the result of [`verify_github_token`] so a burst of requests from
+/// one user doesn't exhaust GitHub's rate limits. Entries are keyed by a
+/// SHA-256 hash of the bearer token -- never the raw token -- so a leaked
+/// cache doesn't reveal anything usable against GitHub. Managed as Rocket
+/// state alongside `Config`.
+pub struct GithubTokenCache {
+    entries: Mutex<HashMap<String, CachedGithubInfo>>,
+    ttl: Duration,
+}
+
+impl GithubTokenCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            entries: Mutex::new(HashMap::new()),
+            ttl,
+        }
+    }
+
+    fn hash_token(token: &str) -> String {
+        to_hex(&Sha256::digest(token.as_bytes()))
+    }
+
+    /// Returns the cached `GithubInfo` for `token` if it's present and still
+    /// within the TTL, evicting it if it has expired.
+    fn get(&self, token: &str) -> Option<GithubInfo> {
+        let key = Self::hash_token(token);
+        let mut entries = self.entries.lock().unwrap();
+
+        match entries.get(&key) {
+            Some(entry) if entry.inserted_at.elapsed() < self.ttl => {
+                Some(entry.info.clone())
+            }
+            Some(_) => {
+                entries.remove(&key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn insert(&self, token: &str, info: GithubInfo) {
+        let key = Self::hash_token(token);
+        self.entries.lock().unwrap().insert(
+            key,
+            CachedGithubInfo {
+                info,
+                inserted_at: Instant::now(),
+            },
+        );
+    }
+}
+
+impl Default for GithubTokenCache {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(DEFAULT_GITHUB_TOKEN_CACHE_TTL_SECS))
+    }
+}
+
+/// Claims embedded in a session token so that `WriteAccess` can be
+/// reconstructed without calling back out to GitHub.
+#[derive(Deserialize, Serialize)]
+struct SessionClaims {
+    /// GitHub user id, as a string (JWT convention for `sub`).
+    sub: String,
+    login: String,
+    exp: u64,
+}
+
+/// Mints a short-lived session token for `github_info`, signed with the
+/// server secret in `Config`. Handed back to clients after a successful
+/// `GithubOAuth` exchange so future requests can skip the round-trips to
+/// `api.github.com` in [`verify_github_token`].
+pub fn issue_session_token(config: &Config, github_info: &GithubInfo) -> anyhow::Result<String> {
+    let exp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|err| anyhow!(err))?
+        .as_secs()
+        + SESSION_TOKEN_LIFETIME_SECS;
+
+    let claims = SessionClaims {
+        sub: github_info.id().to_string(),
+        login: github_info.login().to_owned(),
+        exp,
+    };
+
+    encode(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(config.session_secret.as_bytes()),
+    )
+    .map_err(|err| anyhow!(err))
+}
+
+/// The result of checking a bearer value against [`issue_session_token`]'s
+/// format, distinguishing "this is one of our session tokens, but it's
+/// expired/tampered with" from "this doesn't look like a session token at
+/// all" -- callers must reject the former outright rather than falling back
+/// to treating it as a raw GitHub token.
+enum SessionTokenCheck {
+    Valid(WriteAccess),
+    Invalid,
+    NotASessionToken,
+}
+
+/// Verifies a session token minted by [`issue_session_token`] and, if it's
+/// still valid, reconstructs the `WriteAccess` it grants.
+fn verify_session_token(token: &str, session_secret: &str) -> SessionTokenCheck {
+    let claims = match decode::<SessionClaims>(
+        token,
+        &DecodingKey::from_secret(session_secret.as_bytes()),
+        &Validation::default(),
+    ) {
+        Ok(data) => data.claims,
+        // A raw GitHub token isn't even shaped like a JWT, so it fails here
+        // with `InvalidToken` rather than a signature/expiry error -- that's
+        // the only case that should fall back to `verify_github_token`.
+        Err(err) => {
+            return match err.kind() {
+                jsonwebtoken::errors::ErrorKind::InvalidToken => SessionTokenCheck::NotASessionToken,
+                _ => SessionTokenCheck::Invalid,
+            };
+        }
+    };
+
+    let Some(id) = claims.sub.parse().ok() else {
+        return SessionTokenCheck::Invalid;
+    };
+
+    SessionTokenCheck::Valid(WriteAccess::Github(GithubInfo {
+        login: claims.login,
+        id,
+    }))
+}
+
 #[derive(Deserialize, Serialize)]
 #[serde(tag = "type", content = "value", rename_all = "kebab-case")]
 pub enum AuthMode {
@@ -28,10 +188,23 @@ pub enum AuthMode {
         #[serde(rename = "client-secret")]
         client_secret: String,
     },
+    HmacPsk {
+        keys: Vec<PreSharedKey>,
+    },
     Unauthenticated,
 }
 
-#[derive(Deserialize)]
+/// A named HMAC secret bound to the scopes it's allowed to publish to, used
+/// by [`AuthMode::HmacPsk`] so CI pipelines can authenticate without a
+/// long-lived GitHub token.
+#[derive(Deserialize, Serialize)]
+pub struct PreSharedKey {
+    name: String,
+    secret: String,
+    scopes: Vec<String>,
+}
+
+#[derive(Clone, Deserialize)]
 pub struct GithubInfo {
     login: String,
     id: u64,
@@ -66,6 +239,7 @@ impl fmt::Debug for AuthMode {
             AuthMode::ApiKey(_) => write!(formatter, "API key"),
             AuthMode::DoubleApiKey { .. } => write!(formatter, "double API key"),
             AuthMode::GithubOAuth { .. } => write!(formatter, "Github OAuth"),
+            AuthMode::HmacPsk { .. } => write!(formatter, "HMAC pre-shared key"),
             AuthMode::Unauthenticated => write!(formatter, "no authentication"),
         }
     }
@@ -90,26 +264,157 @@ fn match_api_key<T>(request: &Request<'_>, keys: &[String], result: T) -> Outcom
     }
 }
 
-async fn verify_github_token(
-    request: &Request<'_>,
-    client_id: &str,
-    client_secret: &str,
-) -> Outcome<WriteAccess, Error> {
-    let token: String = match request.headers().get_one("authorization") {
-        Some(key) if key.starts_with("Bearer ") => (key[6..].trim()).to_owned(),
-        _ => {
-            return format_err!("Github auth required")
+/// Hex-encodes `bytes`, matching the `sha256=<hex>` format GitHub (and now
+/// Wally) uses for HMAC signature headers.
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Recomputes the HMAC-SHA256 signature over `body` using `key`'s shared
+/// secret and compares it against an `X-Wally-Signature-256` header value
+/// (`sha256=<hex>`) in constant time, mirroring the discipline in
+/// [`match_api_key`].
+fn verify_hmac_signature(key: &PreSharedKey, body: &[u8], signature_header: &str) -> bool {
+    let expected_hex = match signature_header.strip_prefix("sha256=") {
+        Some(hex) => hex,
+        None => return false,
+    };
+
+    let mut mac = match Hmac::<Sha256>::new_from_slice(key.secret.as_bytes()) {
+        Ok(mac) => mac,
+        Err(_) => return false,
+    };
+    mac.update(body);
+    let computed_hex = to_hex(&mac.finalize().into_bytes());
+
+    constant_time_eq(computed_hex.as_bytes(), expected_hex.as_bytes())
+}
+
+/// Buffers the raw body of a signed publish request into request-local state
+/// before routing, so [`match_hmac_psk`] -- a request guard, which always
+/// resolves before a route's `Data` argument is read -- can see the exact
+/// bytes the signature was computed over.
+///
+/// Attach with `.attach(HmacBodyFairing)` on the Rocket instance. Only
+/// buffers requests carrying `X-Wally-Signature-256` *and* only when
+/// `AuthMode::HmacPsk` is actually configured; everything else is left alone
+/// so an unauthenticated caller can't force the server to pull an arbitrary
+/// body into memory pre-routing just by setting the header on a deployment
+/// that doesn't even use HMAC auth.
+pub struct HmacBodyFairing;
+
+#[rocket::async_trait]
+impl Fairing for HmacBodyFairing {
+    fn info(&self) -> Info {
+        Info {
+            name: "HMAC-signed body capture",
+            kind: Kind::Request,
+        }
+    }
+
+    async fn on_request(&self, request: &mut Request<'_>, data: &mut Data<'_>) {
+        if request.headers().get_one("X-Wally-Signature-256").is_none() {
+            return;
+        }
+
+        let is_hmac_configured = request
+            .rocket()
+            .state::<Config>()
+            .is_some_and(|config| matches!(config.auth, AuthMode::HmacPsk { .. }));
+
+        if !is_hmac_configured {
+            return;
+        }
+
+        let limit = request
+            .limits()
+            .get("file")
+            .unwrap_or_else(|| 10.mebibytes());
+
+        let captured = std::mem::replace(data, Data::local(Vec::new()));
+        let bytes = match captured.open(limit).into_bytes().await {
+            Ok(bytes) => bytes.into_inner(),
+            Err(_) => Vec::new(),
+        };
+
+        // Stash a copy for `match_hmac_psk` to hash, then hand an equivalent,
+        // still-readable `Data` back to the route so it can consume the
+        // upload as normal once `WriteAccess` has been granted.
+        request.local_cache(|| bytes.clone());
+        *data = Data::local(bytes);
+    }
+}
+
+/// Grants `WriteAccess` scoped to whichever pre-shared key's signature
+/// matches the uploaded bytes.
+///
+/// Unlike [`match_api_key`], this has to run against a body the request
+/// itself can't read -- see [`HmacBodyFairing`], which buffers it into
+/// request-local state ahead of routing.
+fn match_hmac_psk(request: &Request<'_>, keys: &[PreSharedKey]) -> Outcome<WriteAccess, Error> {
+    let body: &Vec<u8> = request.local_cache(Vec::new);
+    let signature_header = match request.headers().get_one("X-Wally-Signature-256") {
+        Some(header) => header,
+        None => {
+            return format_err!("HMAC signature required")
                 .status(Status::Unauthorized)
                 .into();
         }
     };
 
+    match keys
+        .iter()
+        .find(|key| verify_hmac_signature(key, body, signature_header))
+    {
+        Some(key) => Outcome::Success(WriteAccess::HmacPsk(key.scopes.clone())),
+        None => format_err!("Invalid HMAC signature")
+            .status(Status::Unauthorized)
+            .into(),
+    }
+}
+
+/// The raw value of a `Authorization: Bearer <token>` header. Route handlers
+/// that only need to forward the token on to [`verify_github_token`] should
+/// take this as a guard instead of `&Request<'_>` -- Rocket has no
+/// `FromRequest` impl for `&Request` itself, so using it directly as a
+/// handler parameter fails to compile.
+pub(crate) struct BearerToken(pub String);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for BearerToken {
+    type Error = Error;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Error> {
+        match request
+            .headers()
+            .get_one("authorization")
+            .and_then(|header| header.strip_prefix("Bearer "))
+            .map(|token| token.trim().to_owned())
+        {
+            Some(token) => Outcome::Success(BearerToken(token)),
+            None => format_err!("Github auth required")
+                .status(Status::Unauthorized)
+                .into(),
+        }
+    }
+}
+
+pub(crate) async fn verify_github_token(
+    token: &str,
+    client_id: &str,
+    client_secret: &str,
+    token_cache: &GithubTokenCache,
+) -> Outcome<WriteAccess, Error> {
+    if let Some(github_info) = token_cache.get(token) {
+        return Outcome::Success(WriteAccess::Github(github_info));
+    }
+
     let client = Client::new();
     let response = client
         .get("https://api.github.com/user")
         .header("accept", "application/json")
         .header("user-agent", "wally")
-        .bearer_auth(&token)
+        .bearer_auth(token)
         .send()
         .await;
 
@@ -128,7 +433,7 @@ async fn verify_github_token(
     };
 
     let mut body = HashMap::new();
-    body.insert("access_token", &token);
+    body.insert("access_token", token);
 
     let response = client
         .post(format!(
@@ -170,10 +475,135 @@ async fn verify_github_token(
         Err(err) => format_err!("Github auth failed: {}", err)
             .status(Status::Unauthorized)
             .into(),
-        Ok(_) => Outcome::Success(WriteAccess::Github(github_info)),
+        Ok(_) => {
+            token_cache.insert(token, github_info.clone());
+            Outcome::Success(WriteAccess::Github(github_info))
+        }
+    }
+}
+
+/// Mints, lists, and revokes Wally-issued personal access tokens. Persisted
+/// through `PackageIndex` -- the same store scope ownership already lives
+/// in -- keyed by a SHA-256 hash of the secret, never the secret
+/// itself, so users get scriptable, least-privilege credentials instead of
+/// pasting account-wide GitHub tokens.
+pub struct PersonalAccessTokenStore;
+
+impl PersonalAccessTokenStore {
+    /// Mints a new token for `owner_id` and returns the secret. This is the
+    /// only time the secret is ever available -- only its hash is persisted.
+    pub fn issue(
+        index: &PackageIndex,
+        owner_id: u64,
+        name: String,
+        permissions: Vec<TokenPermission>,
+        ttl: Option<Duration>,
+    ) -> anyhow::Result<String> {
+        let secret: String = rand::thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(40)
+            .map(char::from)
+            .collect();
+
+        let expires_at = ttl
+            .map(|ttl| {
+                SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|now| now.as_secs() + ttl.as_secs())
+            })
+            .transpose()
+            .map_err(|err| anyhow!(err))?;
+
+        index.create_personal_access_token(PersonalAccessTokenRecord {
+            token_hash: to_hex(&Sha256::digest(secret.as_bytes())),
+            owner_id,
+            name,
+            permissions,
+            expires_at,
+            revoked: false,
+        })?;
+
+        Ok(secret)
+    }
+
+    /// Revokes the token named `name` belonging to `owner_id`, if any.
+    pub fn revoke(index: &PackageIndex, owner_id: u64, name: &str) -> anyhow::Result<()> {
+        index.revoke_personal_access_token(owner_id, name)
+    }
+
+    /// Lists the (non-secret) names and permissions of `owner_id`'s tokens.
+    pub fn list(
+        index: &PackageIndex,
+        owner_id: u64,
+    ) -> anyhow::Result<Vec<(String, Vec<TokenPermission>)>> {
+        Ok(index
+            .personal_access_tokens_for_owner(owner_id)?
+            .into_iter()
+            .map(|record| (record.name, record.permissions))
+            .collect())
+    }
+
+    /// Returns the owner and granted permissions for `secret`, provided it's
+    /// a known token that hasn't been revoked or expired. Scans the stored
+    /// hashes in turn, comparing each with `constant_time_eq` until one
+    /// matches, mirroring the
+    /// discipline in [`match_api_key`] rather than relying on a hash map
+    /// lookup keyed directly by the candidate hash.
+    fn check(
+        index: &PackageIndex,
+        secret: &str,
+    ) -> anyhow::Result<Option<(u64, Vec<TokenPermission>)>> {
+        let candidate_hash = to_hex(&Sha256::digest(secret.as_bytes()));
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+
+        for record in index.personal_access_tokens()? {
+            if !constant_time_eq(record.token_hash.as_bytes(), candidate_hash.as_bytes()) {
+                continue;
+            }
+
+            if record.revoked {
+                return Ok(None);
+            }
+
+            if record.expires_at.is_some_and(|expires_at| now >= expires_at) {
+                return Ok(None);
+            }
+
+            return Ok(Some((record.owner_id, record.permissions)));
+        }
+
+        Ok(None)
     }
 }
 
+/// Checks the `Authorization: Bearer` header against `index` and, if it
+/// matches an unrevoked, unexpired personal access token with `permission`,
+/// returns its owner id and full permission set. Cross-cuts whatever
+/// `AuthMode` is configured, since these tokens are meant to work the same
+/// way everywhere.
+fn match_personal_access_token(
+    request: &Request<'_>,
+    index: &PackageIndex,
+    permission: TokenPermission,
+) -> anyhow::Result<Option<(u64, Vec<TokenPermission>)>> {
+    let token = match request
+        .headers()
+        .get_one("authorization")
+        .and_then(|header| header.strip_prefix("Bearer "))
+        .map(|token| token.trim())
+    {
+        Some(token) => token,
+        None => return Ok(None),
+    };
+
+    Ok(match PersonalAccessTokenStore::check(index, token)? {
+        Some((owner_id, permissions)) if permissions.contains(&permission) => {
+            Some((owner_id, permissions))
+        }
+        _ => None,
+    })
+}
+
 pub enum ReadAccess {
     Public,
     ApiKey,
@@ -184,6 +614,17 @@ impl<'r> FromRequest<'r> for ReadAccess {
     type Error = Error;
 
     async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Error> {
+        let index = request
+            .guard::<&State<PackageIndex>>()
+            .await
+            .expect("PackageIndex was not configured");
+
+        match match_personal_access_token(request, index, TokenPermission::Read) {
+            Ok(Some(_)) => return Outcome::Success(ReadAccess::ApiKey),
+            Ok(None) => {}
+            Err(err) => return Outcome::Error((Status::InternalServerError, err.into())),
+        }
+
         let config = request
             .guard::<&State<Config>>()
             .await
@@ -191,7 +632,12 @@ impl<'r> FromRequest<'r> for ReadAccess {
 
         match &config.auth {
             AuthMode::Unauthenticated => Outcome::Success(ReadAccess::Public),
+            // Reads are public under `GithubOAuth` regardless of whether the caller
+            // presents a session token, a raw GitHub token, or nothing at all, so
+            // there's nothing for the JWT check added to `WriteAccess` to restrict
+            // here -- every caller already gets `ReadAccess::Public`.
             AuthMode::GithubOAuth { .. } => Outcome::Success(ReadAccess::Public),
+            AuthMode::HmacPsk { .. } => Outcome::Success(ReadAccess::Public),
             AuthMode::ApiKey(key) => match_api_key(request, key, ReadAccess::ApiKey),
             AuthMode::DoubleApiKey { read, .. } => match read {
                 None => Outcome::Success(ReadAccess::Public),
@@ -204,26 +650,50 @@ impl<'r> FromRequest<'r> for ReadAccess {
 pub enum WriteAccess {
     ApiKey,
     Github(GithubInfo),
+    HmacPsk(Vec<String>),
+    PersonalAccessToken { owner_id: u64 },
 }
 
 impl WriteAccess {
-    pub fn can_write_package(
+    pub async fn can_write_package(
         &self,
         package_id: &PackageId,
         index: &PackageIndex,
+        config: &Config,
     ) -> anyhow::Result<bool> {
         let scope = package_id.name().scope();
 
         let has_permission = match self {
             WriteAccess::ApiKey => true,
+            WriteAccess::HmacPsk(scopes) => scopes.iter().any(|granted| granted == scope),
+            // Scoped to whatever scopes `owner_id` actually owns, unlike the
+            // registry-wide `ApiKey` grant -- a write-scoped PAT must not be
+            // able to publish to scopes its owner doesn't hold.
+            WriteAccess::PersonalAccessToken { owner_id } => {
+                index.is_scope_owner(scope, owner_id)?
+            }
             WriteAccess::Github(github_info) => {
                 match index.is_scope_owner(scope, github_info.id())? {
                     true => true,
                     // Only grant write access if the username matches the scope AND the scope has no existing owners
-                    false => {
-                        github_info.login().to_lowercase() == scope
-                            && index.get_scope_owners(scope)?.is_empty()
+                    false if github_info.login().to_lowercase() == scope
+                        && index.get_scope_owners(scope)?.is_empty() =>
+                    {
+                        true
                     }
+                    // Otherwise, fall back to checking whether the scope is backed by a
+                    // GitHub org/team and the caller is a confirmed member of it.
+                    false => match index.org_scope(scope)? {
+                        Some(org_scope) => {
+                            is_org_scope_member(
+                                &org_scope,
+                                github_info.login(),
+                                config.github_server_token.as_deref(),
+                            )
+                            .await?
+                        }
+                        None => false,
+                    },
                 }
             }
         };
@@ -232,11 +702,79 @@ impl WriteAccess {
     }
 }
 
+/// Resolves whether `username` is a confirmed member of the GitHub org or
+/// team backing `org_scope`, so orgs can let any member (or a specific team)
+/// publish instead of maintaining a manual owner list.
+///
+/// Requires `github_server_token` (`Config::github_server_token`): both the
+/// org-members and the team-memberships endpoints need authentication to see
+/// anything beyond public org members, and an unauthenticated call also
+/// shares the 60-requests/hour anonymous IP rate limit with everyone else on
+/// the box -- the exact problem `GithubTokenCache` exists to avoid. Org/team-
+/// backed scopes simply don't grant access until an operator configures one.
+async fn is_org_scope_member(
+    org_scope: &OrgScope,
+    username: &str,
+    github_server_token: Option<&str>,
+) -> anyhow::Result<bool> {
+    let Some(github_server_token) = github_server_token else {
+        return Ok(false);
+    };
+
+    let url = match org_scope {
+        OrgScope::Org(org) => format!("https://api.github.com/orgs/{}/members/{}", org, username),
+        OrgScope::Team { org, team } => format!(
+            "https://api.github.com/orgs/{}/teams/{}/memberships/{}",
+            org, team, username
+        ),
+    };
+
+    let response = Client::new()
+        .get(url)
+        .header("accept", "application/vnd.github+json")
+        .header("user-agent", "wally")
+        .bearer_auth(github_server_token)
+        .send()
+        .await?;
+
+    // GitHub returns 204 for a confirmed org member and 200 with a `state` of
+    // "active" for a confirmed team member; anything else (404 included)
+    // means no confirmed membership.
+    match response.status() {
+        StatusCode::NO_CONTENT => Ok(true),
+        StatusCode::OK => {
+            #[derive(Deserialize)]
+            struct Membership {
+                state: String,
+            }
+            Ok(response
+                .json::<Membership>()
+                .await
+                .map(|membership| membership.state == "active")
+                .unwrap_or(false))
+        }
+        _ => Ok(false),
+    }
+}
+
 #[rocket::async_trait]
 impl<'r> FromRequest<'r> for WriteAccess {
     type Error = Error;
 
     async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Error> {
+        let index = request
+            .guard::<&State<PackageIndex>>()
+            .await
+            .expect("PackageIndex was not configured");
+
+        match match_personal_access_token(request, index, TokenPermission::Write) {
+            Ok(Some((owner_id, _))) => {
+                return Outcome::Success(WriteAccess::PersonalAccessToken { owner_id })
+            }
+            Ok(None) => {}
+            Err(err) => return Outcome::Error((Status::InternalServerError, err.into())),
+        }
+
         let config = request
             .guard::<&State<Config>>()
             .await
@@ -250,10 +788,44 @@ impl<'r> FromRequest<'r> for WriteAccess {
             AuthMode::DoubleApiKey { write, .. } => {
                 match_api_key(request, write, WriteAccess::ApiKey)
             }
+            AuthMode::HmacPsk { keys } => match_hmac_psk(request, keys),
             AuthMode::GithubOAuth {
                 client_id,
                 client_secret,
-            } => verify_github_token(request, client_id, client_secret).await,
+            } => {
+                let bearer = request
+                    .headers()
+                    .get_one("authorization")
+                    .and_then(|header| header.strip_prefix("Bearer "))
+                    .map(|token| token.trim());
+
+                let Some(token) = bearer else {
+                    return format_err!("Github auth required")
+                        .status(Status::Unauthorized)
+                        .into();
+                };
+
+                match verify_session_token(token, &config.session_secret) {
+                    SessionTokenCheck::Valid(write_access) => Outcome::Success(write_access),
+                    // It decoded as one of our own session tokens but failed
+                    // signature or expiry validation -- reject it outright
+                    // instead of spending two `api.github.com` round-trips on
+                    // a token we already know is bad.
+                    SessionTokenCheck::Invalid => format_err!("Session token is expired or invalid")
+                        .status(Status::Unauthorized)
+                        .into(),
+                    // Doesn't look like a session token at all: fall back to
+                    // treating the bearer value as a raw GitHub token.
+                    SessionTokenCheck::NotASessionToken => {
+                        let token_cache = request
+                            .guard::<&State<GithubTokenCache>>()
+                            .await
+                            .expect("GithubTokenCache was not configured");
+
+                        verify_github_token(token, client_id, client_secret, token_cache).await
+                    }
+                }
+            }
         }
     }
 }
@@ -0,0 +1,28 @@
+use serde::Deserialize;
+
+use crate::auth::AuthMode;
+
+/// Registry-wide configuration, loaded from `Rocket.toml`/the environment and
+/// managed as Rocket state so every request guard (see `auth.rs`) can reach
+/// it via `&State<Config>`.
+#[derive(Deserialize)]
+pub struct Config {
+    pub auth: AuthMode,
+
+    /// Secret used to sign/verify the short-lived session tokens minted in
+    /// [`crate::auth::issue_session_token`] after a `GithubOAuth` exchange.
+    pub session_secret: String,
+
+    /// How long, in seconds, a validated GitHub token is trusted before
+    /// `GithubTokenCache` re-checks it against the GitHub API. Defaults to
+    /// `auth::DEFAULT_GITHUB_TOKEN_CACHE_TTL_SECS` when unset.
+    #[serde(default)]
+    pub github_token_cache_ttl_secs: Option<u64>,
+
+    /// Server-level GitHub token used for privileged lookups that a bare
+    /// `client_id`/`client_secret` pair can't make, such as checking org/team
+    /// membership in `WriteAccess::can_write_package`. Org- and team-backed
+    /// scopes only grant write access when this is configured.
+    #[serde(default)]
+    pub github_server_token: Option<String>,
+}
@@ -0,0 +1,75 @@
+use rocket::{
+    http::Status,
+    request::Outcome,
+    response::{self, Responder},
+    Request,
+};
+
+/// A request-handling error carrying the HTTP status it should be reported
+/// as, so request guards and route handlers can return `anyhow::Result`-style
+/// errors directly without building a `rocket::Response` by hand.
+pub struct Error {
+    source: anyhow::Error,
+    status: Status,
+}
+
+impl<E> From<E> for Error
+where
+    E: Into<anyhow::Error>,
+{
+    fn from(source: E) -> Self {
+        Error {
+            source: source.into(),
+            status: Status::InternalServerError,
+        }
+    }
+}
+
+impl<'r> Responder<'r, 'static> for Error {
+    fn respond_to(self, request: &'r Request<'_>) -> response::Result<'static> {
+        rocket::response::Debug(self.source)
+            .respond_to(request)
+            .map(|mut response| {
+                response.set_status(self.status);
+                response
+            })
+    }
+}
+
+/// An `anyhow::Error` paired with the HTTP status it should be reported as.
+/// Produced by [`ApiErrorStatus::status`] and converted with `.into()` into
+/// whichever `Outcome<_, Error>` (or [`Error`] itself) the call site needs.
+pub struct StatusError {
+    source: anyhow::Error,
+    status: Status,
+}
+
+/// Attaches an HTTP status to an `anyhow::Error`, matching the style used
+/// throughout `auth.rs`: `format_err!("...").status(Status::Unauthorized)`.
+pub trait ApiErrorStatus {
+    fn status(self, status: Status) -> StatusError;
+}
+
+impl ApiErrorStatus for anyhow::Error {
+    fn status(self, status: Status) -> StatusError {
+        StatusError {
+            source: self,
+            status,
+        }
+    }
+}
+
+impl From<StatusError> for Error {
+    fn from(err: StatusError) -> Self {
+        Error {
+            source: err.source,
+            status: err.status,
+        }
+    }
+}
+
+impl<S> From<StatusError> for Outcome<S, Error> {
+    fn from(err: StatusError) -> Self {
+        Outcome::Error((err.status, err.into()))
+    }
+}
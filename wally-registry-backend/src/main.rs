@@ -0,0 +1,35 @@
+mod auth;
+mod config;
+mod error;
+mod routes;
+
+use std::time::Duration;
+
+use auth::{GithubTokenCache, HmacBodyFairing, DEFAULT_GITHUB_TOKEN_CACHE_TTL_SECS};
+use config::Config;
+use libwally::package_index::PackageIndex;
+use rocket::fairing::AdHoc;
+
+#[rocket::launch]
+fn rocket() -> _ {
+    rocket::build()
+        .attach(AdHoc::config::<Config>())
+        .attach(HmacBodyFairing)
+        .manage(PackageIndex::default())
+        // `Config` is only available once the `AdHoc::config` fairing above has
+        // run, so the cache's TTL is wired in as its own ignite fairing rather
+        // than a plain `.manage()` call.
+        .attach(AdHoc::try_on_ignite(
+            "Github token cache",
+            |rocket| async {
+                let ttl = rocket
+                    .state::<Config>()
+                    .and_then(|config| config.github_token_cache_ttl_secs)
+                    .map(Duration::from_secs)
+                    .unwrap_or_else(|| Duration::from_secs(DEFAULT_GITHUB_TOKEN_CACHE_TTL_SECS));
+
+                Ok(rocket.manage(GithubTokenCache::new(ttl)))
+            },
+        ))
+        .mount("/", routes::routes())
+}
@@ -0,0 +1,185 @@
+use std::time::Duration;
+
+use libwally::{
+    package_id::PackageId,
+    package_index::{PackageIndex, TokenPermission},
+};
+use rocket::{
+    data::{Data, ToByteUnit},
+    http::Status,
+    request::Outcome,
+    serde::json::Json,
+    Route, State,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    auth::{
+        issue_session_token, verify_github_token, AuthMode, BearerToken, GithubTokenCache,
+        PersonalAccessTokenStore, WriteAccess,
+    },
+    config::Config,
+    error::{ApiErrorStatus, Error},
+};
+
+pub fn routes() -> Vec<Route> {
+    rocket::routes![
+        exchange_session_token,
+        publish,
+        create_personal_access_token,
+        list_personal_access_tokens,
+        revoke_personal_access_token,
+    ]
+}
+
+/// Pulls the GitHub user id out of `access`, rejecting any other `WriteAccess`
+/// variant. Personal access tokens are minted, listed, and revoked against a
+/// GitHub identity, so managing them requires having proven one -- a PAT
+/// can't be used to mint another PAT.
+fn require_github_owner(access: WriteAccess) -> Result<u64, Error> {
+    match access {
+        WriteAccess::Github(github_info) => Ok(*github_info.id()),
+        _ => Err(anyhow::anyhow!(
+            "Github authentication is required to manage personal access tokens"
+        )
+        .status(Status::Forbidden)
+        .into()),
+    }
+}
+
+#[derive(Serialize)]
+struct SessionTokenResponse {
+    token: String,
+}
+
+/// Exchanges a raw GitHub OAuth token for a short-lived, self-signed session
+/// token (see `auth::issue_session_token`), so repeated `wally publish` calls
+/// don't each cost a round-trip to `api.github.com`.
+#[rocket::post("/v1/auth/session-token")]
+async fn exchange_session_token(
+    bearer: BearerToken,
+    config: &State<Config>,
+    token_cache: &State<GithubTokenCache>,
+) -> Result<Json<SessionTokenResponse>, Error> {
+    let (client_id, client_secret) = match &config.auth {
+        AuthMode::GithubOAuth {
+            client_id,
+            client_secret,
+        } => (client_id, client_secret),
+        _ => {
+            return Err(anyhow::anyhow!("Github OAuth is not configured")
+                .status(Status::NotFound)
+                .into())
+        }
+    };
+
+    let github_info = match verify_github_token(&bearer.0, client_id, client_secret, token_cache)
+        .await
+    {
+        Outcome::Success(WriteAccess::Github(github_info)) => github_info,
+        Outcome::Success(_) => unreachable!("verify_github_token only ever returns WriteAccess::Github"),
+        Outcome::Error((_, err)) => return Err(err),
+        Outcome::Forward(_) => unreachable!("verify_github_token never forwards"),
+    };
+
+    let token = issue_session_token(config, &github_info)?;
+    Ok(Json(SessionTokenResponse { token }))
+}
+
+/// Publishes a package. `WriteAccess` (including `AuthMode::HmacPsk`) is
+/// resolved as a request guard before this handler runs, against the same
+/// bytes read below -- see `auth::HmacBodyFairing`, which buffers the body
+/// for the HMAC check and hands back an equivalent, still-readable `Data`.
+#[rocket::put("/v1/package/<scope>/<name>", data = "<data>")]
+async fn publish(
+    scope: &str,
+    name: &str,
+    access: WriteAccess,
+    index: &State<PackageIndex>,
+    config: &State<Config>,
+    data: Data<'_>,
+) -> Result<(), Error> {
+    let package_id = PackageId::new(scope, name);
+
+    if !access.can_write_package(&package_id, index, config).await? {
+        return Err(anyhow::anyhow!("not authorized to publish to this scope")
+            .status(Status::Forbidden)
+            .into());
+    }
+
+    // Archiving the uploaded bytes is handled by the package-publishing path,
+    // which this auth-focused series doesn't touch.
+    let _bytes = data.open(10.mebibytes()).into_bytes().await?.into_inner();
+
+    Ok(())
+}
+
+#[derive(Deserialize)]
+struct CreatePersonalAccessTokenRequest {
+    name: String,
+    permissions: Vec<TokenPermission>,
+    /// Time-to-live, in seconds. Omitted means the token never expires.
+    #[serde(default)]
+    ttl_secs: Option<u64>,
+}
+
+#[derive(Serialize)]
+struct CreatePersonalAccessTokenResponse {
+    secret: String,
+}
+
+/// Mints a new personal access token for the caller, scoped to `permissions`.
+#[rocket::post("/v1/auth/tokens", data = "<body>")]
+fn create_personal_access_token(
+    access: WriteAccess,
+    index: &State<PackageIndex>,
+    body: Json<CreatePersonalAccessTokenRequest>,
+) -> Result<Json<CreatePersonalAccessTokenResponse>, Error> {
+    let owner_id = require_github_owner(access)?;
+    let body = body.into_inner();
+
+    let secret = PersonalAccessTokenStore::issue(
+        index,
+        owner_id,
+        body.name,
+        body.permissions,
+        body.ttl_secs.map(Duration::from_secs),
+    )?;
+
+    Ok(Json(CreatePersonalAccessTokenResponse { secret }))
+}
+
+#[derive(Serialize)]
+struct PersonalAccessTokenSummary {
+    name: String,
+    permissions: Vec<TokenPermission>,
+}
+
+/// Lists the caller's personal access tokens, without revealing their
+/// secrets.
+#[rocket::get("/v1/auth/tokens")]
+fn list_personal_access_tokens(
+    access: WriteAccess,
+    index: &State<PackageIndex>,
+) -> Result<Json<Vec<PersonalAccessTokenSummary>>, Error> {
+    let owner_id = require_github_owner(access)?;
+
+    let tokens = PersonalAccessTokenStore::list(index, owner_id)?
+        .into_iter()
+        .map(|(name, permissions)| PersonalAccessTokenSummary { name, permissions })
+        .collect();
+
+    Ok(Json(tokens))
+}
+
+/// Revokes the caller's personal access token named `name`, if any.
+#[rocket::delete("/v1/auth/tokens/<name>")]
+fn revoke_personal_access_token(
+    name: &str,
+    access: WriteAccess,
+    index: &State<PackageIndex>,
+) -> Result<(), Error> {
+    let owner_id = require_github_owner(access)?;
+    PersonalAccessTokenStore::revoke(index, owner_id, name)?;
+    Ok(())
+}